@@ -0,0 +1,47 @@
+//! Minidump capture for targets that die during injection
+
+use std::os::windows::io::AsRawHandle;
+use std::path::{Path, PathBuf};
+
+use windows::Win32::Foundation::HANDLE;
+use windows::Win32::System::Diagnostics::Debug::{
+    MINIDUMP_TYPE, MiniDumpWithFullMemory, MiniDumpWithThreadInfo, MiniDumpWriteDump,
+};
+
+/// Write a minidump of `process` into `dir`, returning the path to the `.dmp` file.
+///
+/// The dump is named after the PID and includes full memory plus thread info,
+/// which is enough to analyse an anti-tamper routine killing the payload.
+///
+/// `process` is the handle we already hold from injection: the target is
+/// typically mid-crash by the time this runs, so its PID may no longer be
+/// openable, but the handle we opened earlier still resolves the exiting
+/// process.
+pub fn write_minidump(process: HANDLE, pid: u32, dir: &Path) -> windows::core::Result<PathBuf> {
+    // The caller passes the handle it holds from injection; on the paths where
+    // injection never opened one it is null, and `MiniDumpWriteDump` would fail
+    // deep inside DbgHelp with an opaque error. Reject it up front instead.
+    if process.0.is_null() {
+        return Err(windows::core::Error::new(
+            windows::core::HRESULT(-1),
+            "no process handle available for crash dump",
+        ));
+    }
+
+    std::fs::create_dir_all(dir).map_err(|e| {
+        windows::core::Error::new(windows::core::HRESULT(-1), e.to_string())
+    })?;
+
+    let dump_path = dir.join(format!("crash_{}.dmp", pid));
+
+    let file = std::fs::File::create(&dump_path).map_err(|e| {
+        windows::core::Error::new(windows::core::HRESULT(-1), e.to_string())
+    })?;
+    let file_handle = HANDLE(file.as_raw_handle() as _);
+
+    let dump_type: MINIDUMP_TYPE = MiniDumpWithFullMemory | MiniDumpWithThreadInfo;
+
+    unsafe { MiniDumpWriteDump(process, pid, file_handle, dump_type, None, None, None)? };
+
+    Ok(dump_path)
+}
@@ -0,0 +1,183 @@
+//! Central logger - colored console output with optional NDJSON log file
+
+use std::fs::File;
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use colored::Colorize;
+use uwpdumper_shared::LogLevel;
+
+/// Console verbosity threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verbosity {
+    /// Suppress Info and Success on the console (warnings and errors only).
+    Quiet,
+    /// Default: show everything at Info and above.
+    Normal,
+    /// Everything Normal shows, plus `Debug` diagnostics.
+    Verbose,
+}
+
+/// A single log severity, unifying packet log levels with completion/fatal events.
+#[derive(Debug, Clone, Copy)]
+pub enum Severity {
+    /// Extra diagnostic detail, shown on the console only in `Verbose`.
+    Debug,
+    Info,
+    Success,
+    Warning,
+    Error,
+    /// Dump completed.
+    Complete,
+    /// Fatal dumper failure.
+    Fatal,
+}
+
+impl Severity {
+    fn from_log_level(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Info => Severity::Info,
+            LogLevel::Success => Severity::Success,
+            LogLevel::Warning => Severity::Warning,
+            LogLevel::Error => Severity::Error,
+        }
+    }
+
+    /// Lowercase tag used in JSON records.
+    fn as_str(self) -> &'static str {
+        match self {
+            Severity::Debug => "debug",
+            Severity::Info => "info",
+            Severity::Success => "success",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Complete => "complete",
+            Severity::Fatal => "fatal",
+        }
+    }
+
+    /// Colored console prefix, matching the historical `[INFO]`-style tags.
+    fn console_prefix(self) -> colored::ColoredString {
+        match self {
+            Severity::Debug => "[DEBUG]".dimmed(),
+            Severity::Info => "[INFO]".blue(),
+            Severity::Success => "[OK]".green(),
+            Severity::Warning => "[WARN]".yellow(),
+            Severity::Error => "[ERROR]".red(),
+            Severity::Complete => "[DONE]".green().bold(),
+            Severity::Fatal => "[FATAL]".red().bold(),
+        }
+    }
+
+    /// Whether this severity is shown on the console at the given verbosity.
+    /// `Debug` appears only in `Verbose`; Info/Success are suppressed in
+    /// `Quiet`. Every severity is always persisted to the log file.
+    fn shown_at(self, verbosity: Verbosity) -> bool {
+        match verbosity {
+            Verbosity::Quiet => !matches!(self, Severity::Debug | Severity::Info | Severity::Success),
+            Verbosity::Normal => !matches!(self, Severity::Debug),
+            Verbosity::Verbose => true,
+        }
+    }
+}
+
+/// Central logger shared across the dump.
+pub struct Logger {
+    verbosity: Verbosity,
+    pid: u32,
+    file: Option<Mutex<File>>,
+}
+
+impl Logger {
+    /// Create a logger, opening `log_file` for newline-delimited JSON output
+    /// when a path is supplied.
+    pub fn new(verbosity: Verbosity, pid: u32, log_file: Option<&str>) -> Self {
+        let file = log_file.and_then(|path| match File::create(path) {
+            Ok(f) => Some(Mutex::new(f)),
+            Err(e) => {
+                eprintln!("{} Could not open log file {}: {}", "[WARN]".yellow(), path, e);
+                None
+            }
+        });
+        Self {
+            verbosity,
+            pid,
+            file,
+        }
+    }
+
+    /// Log a message at the given severity, optionally carrying a file count
+    /// (set on completion events).
+    pub fn log(&self, severity: Severity, message: &str, file_count: Option<u64>) {
+        if severity.shown_at(self.verbosity) {
+            // Clear any in-progress progress line before printing.
+            crate::clear_progress_line();
+            if matches!(severity, Severity::Error | Severity::Fatal) {
+                eprintln!("{} {}", severity.console_prefix(), message);
+            } else {
+                println!("{} {}", severity.console_prefix(), message);
+            }
+        }
+
+        if let Some(file) = &self.file {
+            self.write_json(file, severity, message, file_count);
+        }
+    }
+
+    /// Write one NDJSON record to the log file.
+    fn write_json(&self, file: &Mutex<File>, severity: Severity, message: &str, file_count: Option<u64>) {
+        let ts = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+
+        let record = serde_json::json!({
+            "timestamp": ts,
+            "pid": self.pid,
+            "level": severity.as_str(),
+            "message": message,
+            "file_count": file_count,
+        });
+
+        if let Ok(mut f) = file.lock() {
+            let _ = writeln!(f, "{}", record);
+        }
+    }
+
+    /// Convenience wrapper for a packet log level.
+    pub fn log_level(&self, level: LogLevel, message: &str) {
+        self.log(Severity::from_log_level(level), message, None);
+    }
+
+    /// Like [`Logger::log_level`] but carrying a file count, used for the
+    /// completion summary line so its JSON record reports `file_count`.
+    pub fn log_level_with_count(&self, level: LogLevel, message: &str, file_count: Option<u64>) {
+        self.log(Severity::from_log_level(level), message, file_count);
+    }
+
+    /// Log a diagnostic message, shown on the console only with `--verbose`.
+    pub fn debug(&self, message: &str) {
+        self.log(Severity::Debug, message, None);
+    }
+
+    /// Log an informational message.
+    pub fn info(&self, message: &str) {
+        self.log(Severity::Info, message, None);
+    }
+
+    /// Log a success message.
+    pub fn success(&self, message: &str) {
+        self.log(Severity::Success, message, None);
+    }
+
+    /// Log a warning.
+    pub fn warn(&self, message: &str) {
+        self.log(Severity::Warning, message, None);
+    }
+
+    /// Log an error.
+    pub fn error(&self, message: &str) {
+        self.log(Severity::Error, message, None);
+    }
+}
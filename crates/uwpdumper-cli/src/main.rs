@@ -1,6 +1,8 @@
 //! UWPDumper CLI - injects DLL into UWP processes and displays output
 
+mod crashdump;
 mod inject;
+mod logger;
 mod package;
 mod process;
 
@@ -8,8 +10,8 @@ use clap::Parser;
 use colored::Colorize;
 use std::io::{self, Write};
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
-use uwpdumper_shared::{IpcHost, LogLevel, Packet, PacketId};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use uwpdumper_shared::{IpcHost, Packet, PacketId};
 
 #[derive(Parser)]
 #[command(name = "uwpdumper")]
@@ -35,11 +37,170 @@ struct Args {
     /// Custom output directory for dumped files
     #[arg(short, long)]
     output: Option<String>,
+
+    /// Dump the whole UWP process tree sharing the target's package family,
+    /// merging each process's files under a per-PID subfolder
+    #[arg(long, visible_alias = "recurse")]
+    tree: bool,
+
+    /// Write a minidump of the target to this directory if it crashes during
+    /// injection
+    #[arg(long = "crash-dump", value_name = "DIR")]
+    crash_dump: Option<String>,
+
+    /// Show additional diagnostic output
+    #[arg(short, long, conflicts_with = "quiet")]
+    verbose: bool,
+
+    /// Suppress informational output (warnings and errors only)
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Write a newline-delimited JSON log of every event to this file
+    #[arg(long = "log-file", value_name = "PATH")]
+    log_file: Option<String>,
+
+    /// Pack the dump into a single compressed archive (.zip or .tar.zst)
+    /// instead of leaving loose files
+    #[arg(long, value_name = "FILE")]
+    archive: Option<String>,
+
+    /// How the injected payload delivers files inside the sandbox:
+    /// stream (default), copy, archive, or archive-deflate
+    #[arg(long = "payload-mode", value_name = "MODE", default_value = "stream")]
+    payload_mode: String,
+
+    /// Ask the payload to verify the loose-file dump against its manifest
+    /// (re-reads every file, so it doubles I/O). Requires `--payload-mode copy`,
+    /// since only that path writes the loose tree the manifest describes.
+    #[arg(long = "verify-dump")]
+    verify_dump: bool,
+}
+
+/// Destination archive for the dump, set from `--archive`. `None` leaves loose
+/// files in the output directory.
+static ARCHIVE_PATH: std::sync::OnceLock<Option<PathBuf>> = std::sync::OnceLock::new();
+
+/// Dump mode the payload should use, selected by `--payload-mode`. Sent to the
+/// payload over IPC before the dump starts, since the injected DLL never
+/// inherits the injector's environment.
+static PAYLOAD_MODE: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+/// Whether the payload should verify its dump against the manifest, set from
+/// `--verify-dump`. Travels over the same IPC control channel as the mode.
+static VERIFY_DUMP: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+
+/// Send the payload its dump configuration over IPC, then release it to start.
+///
+/// Centralises the host side of the control channel so every dump path -
+/// single, suspended and tree - hands the payload the same selection.
+fn send_config_and_start(ipc: &mut IpcHost) {
+    if let Some(mode) = PAYLOAD_MODE.get() {
+        ipc.set_dump_mode(mode);
+    }
+    ipc.set_verify(VERIFY_DUMP.get().copied().unwrap_or(false));
+    ipc.start_dump();
+}
+
+/// Process-wide logger, initialised from CLI args at startup.
+static LOGGER: std::sync::OnceLock<logger::Logger> = std::sync::OnceLock::new();
+
+/// Access the global logger, falling back to a default console logger if it
+/// was never initialised (e.g. early errors before `main` wires it up).
+fn log() -> &'static logger::Logger {
+    LOGGER.get_or_init(|| logger::Logger::new(logger::Verbosity::Normal, 0, None))
+}
+
+/// Directory to write a minidump into if the target crashes, set from
+/// `--crash-dump`. `None` disables crash capture.
+static CRASH_DUMP_DIR: std::sync::OnceLock<Option<PathBuf>> = std::sync::OnceLock::new();
+
+/// Write a minidump of `process` if `--crash-dump` was supplied.
+///
+/// Uses the handle we still hold from injection rather than re-opening the PID,
+/// which usually fails once the target has started exiting.
+fn maybe_write_crash_dump(process: &inject::ProcessHandle, pid: u32) {
+    let Some(Some(dir)) = CRASH_DUMP_DIR.get() else {
+        return;
+    };
+    match crashdump::write_minidump(process.handle(), pid, dir) {
+        Ok(path) => log().info(&format!("Wrote crash dump: {}", path.display())),
+        Err(e) => log().warn(&format!("Failed to write crash dump: {}", e)),
+    }
+}
+
+/// PID of the process we've suspended but not yet resumed, or 0 if none.
+///
+/// Read by the Ctrl-C / console-close handler so an interrupted dump resumes
+/// the target instead of leaving it frozen for Task Manager to clean up.
+static SUSPENDED_PID: AtomicU32 = AtomicU32::new(0);
+
+/// Record that `pid` has been suspended so the signal handler can resume it.
+fn mark_suspended(pid: u32) {
+    SUSPENDED_PID.store(pid, Ordering::SeqCst);
+}
+
+/// Clear the suspended-PID guard once the process has been resumed, or once the
+/// dump has finished (the suspended launch path does not resume the target
+/// after a completed dump).
+fn clear_suspended() {
+    SUSPENDED_PID.store(0, Ordering::SeqCst);
+}
+
+/// Install a Ctrl-C / console-close handler that resumes any suspended target
+/// before exiting, so partial dumps are safely abortable.
+fn install_signal_handler() {
+    let result = ctrlc::set_handler(|| {
+        clear_progress_line();
+        let pid = SUSPENDED_PID.swap(0, Ordering::SeqCst);
+        if pid != 0 {
+            log().warn(&format!("\nInterrupted - resuming suspended process {}...", pid));
+            let _ = inject::resume_process(pid);
+        }
+        // Exit promptly; the IPC host's OS handles are released on process exit.
+        std::process::exit(130);
+    });
+
+    if let Err(e) = result {
+        log().warn(&format!("Could not install Ctrl-C handler: {}", e));
+    }
 }
 
 fn main() {
     let args = Args::parse();
 
+    // `--verify-dump` only does anything on the loose-file copy path: the
+    // manifest it re-reads is written there, and the default stream/archive
+    // paths never call into verification. Reject it up front rather than
+    // accepting a flag that would quietly do nothing.
+    if args.verify_dump && !matches!(args.payload_mode.as_str(), "copy" | "local") {
+        eprintln!("error: --verify-dump requires --payload-mode copy");
+        std::process::exit(2);
+    }
+
+    // The injected payload can't read this process's environment (UWP targets
+    // are activated via DCOM; attach targets already exist), so the dump mode
+    // travels over the IPC channel instead - see send_config_and_start.
+    let _ = PAYLOAD_MODE.set(args.payload_mode.clone());
+
+    install_signal_handler();
+
+    let _ = CRASH_DUMP_DIR.set(args.crash_dump.as_deref().map(PathBuf::from));
+    let _ = ARCHIVE_PATH.set(args.archive.as_deref().map(PathBuf::from));
+
+    let verbosity = if args.quiet {
+        logger::Verbosity::Quiet
+    } else if args.verbose {
+        logger::Verbosity::Verbose
+    } else {
+        logger::Verbosity::Normal
+    };
+    let _ = LOGGER.set(logger::Logger::new(
+        verbosity,
+        args.pid.unwrap_or(0),
+        args.log_file.as_deref(),
+    ));
+
     print_banner();
 
     // Handle --list flag
@@ -59,6 +220,8 @@ fn main() {
         Some(p) => p,
         None => return,
     };
+    log().debug(&format!("Payload DLL: {}", dll_path.display()));
+    log().debug(&format!("Dump mode: {}", args.payload_mode));
 
     // Determine target process
     let target = if args.pid.is_some() || args.name.is_some() {
@@ -66,13 +229,9 @@ fn main() {
             Some(p) => p,
             None => {
                 if let Some(pid) = args.pid {
-                    eprintln!("{} No UWP process found with PID: {}", "[ERROR]".red(), pid);
+                    log().error(&format!("No UWP process found with PID: {}", pid));
                 } else if let Some(ref name) = args.name {
-                    eprintln!(
-                        "{} No UWP process found matching: {}",
-                        "[ERROR]".red(),
-                        name
-                    );
+                    log().error(&format!("No UWP process found matching: {}", name));
                 }
                 return;
             }
@@ -85,14 +244,14 @@ fn main() {
         }
     };
 
-    println!(
-        "\n{} Selected: {} (PID: {})",
-        "[INFO]".blue(),
-        target.name,
-        target.pid
-    );
+    log().info(&format!("\nSelected: {} (PID: {})", target.name, target.pid));
+    log().debug(&format!("Package family: {}", target.family_name));
 
-    inject_and_dump(target.pid, &dll_path, args.output.as_deref());
+    if args.tree {
+        inject_and_dump_tree(&target, &dll_path, args.output.as_deref());
+    } else {
+        inject_and_dump(target.pid, &dll_path, args.output.as_deref());
+    }
 }
 
 fn print_banner() {
@@ -114,23 +273,19 @@ fn get_dll_path() -> Option<PathBuf> {
     if dll_path.exists() {
         Some(dll_path)
     } else {
-        eprintln!(
-            "{} DLL not found at: {}",
-            "[ERROR]".red(),
-            dll_path.display()
-        );
+        log().error(&format!("DLL not found at: {}", dll_path.display()));
         eprintln!("Make sure uwpdumper_payload.dll is in the same directory as this executable.");
         None
     }
 }
 
 fn list_packages_command() {
-    println!("{} Listing installed UWP packages...\n", "[INFO]".blue());
+    log().info("Listing installed UWP packages...\n");
 
     match package::list_packages() {
         Ok(mut packages) => {
             if packages.is_empty() {
-                println!("{} No UWP packages found.", "[WARN]".yellow());
+                log().warn("No UWP packages found.");
             } else {
                 // Sort alphabetically by display name (case-insensitive)
                 packages.sort_by(|a, b| {
@@ -153,58 +308,50 @@ fn list_packages_command() {
             }
         }
         Err(e) => {
-            eprintln!("{} Failed to list packages: {}", "[ERROR]".red(), e);
+            log().error(&format!("Failed to list packages: {}", e));
         }
     }
 }
 
 fn launch_and_dump(pkg_name: &str, output_path: Option<&str>) {
     // Find the package
-    println!("{} Looking for package: {}", "[INFO]".blue(), pkg_name);
+    log().info(&format!("Looking for package: {}", pkg_name));
 
     let pkg = match package::find_package(pkg_name) {
         Ok(Some(p)) => p,
         Ok(None) => {
-            eprintln!(
-                "{} No package found matching: {}",
-                "[ERROR]".red(),
-                pkg_name
-            );
+            log().error(&format!("No package found matching: {}", pkg_name));
             return;
         }
         Err(e) => {
-            eprintln!("{} Failed to find package: {}", "[ERROR]".red(), e);
+            log().error(&format!("Failed to find package: {}", e));
             return;
         }
     };
 
-    println!(
-        "{} Found: {} ({})",
-        "[OK]".green(),
-        pkg.name,
-        pkg.family_name
-    );
+    log().success(&format!("Found: {} ({})", pkg.name, pkg.family_name));
 
-    println!("{} Launching application...", "[INFO]".blue());
+    log().info("Launching application...");
 
     // Launch the app
     let pid = match package::launch_package(&pkg) {
         Ok(pid) => pid,
         Err(e) => {
-            eprintln!("{} Failed to launch package: {}", "[ERROR]".red(), e);
+            log().error(&format!("Failed to launch package: {}", e));
             return;
         }
     };
 
-    println!("{} Launched with PID: {}", "[OK]".green(), pid);
+    log().success(&format!("Launched with PID: {}", pid));
 
     // Immediately suspend the process before it can do anything
-    println!("{} Suspending process...", "[INFO]".blue());
+    log().info("Suspending process...");
     if let Err(e) = inject::suspend_process(pid) {
-        eprintln!("{} Failed to suspend process: {}", "[ERROR]".red(), e);
+        log().error(&format!("Failed to suspend process: {}", e));
         return;
     }
-    println!("{} Process suspended", "[OK]".green());
+    mark_suspended(pid);
+    log().success("Process suspended");
 
     // Now inject and dump while suspended
     let dll_path = match get_dll_path() {
@@ -212,6 +359,7 @@ fn launch_and_dump(pkg_name: &str, output_path: Option<&str>) {
         None => {
             // Resume process before returning on error
             let _ = inject::resume_process(pid);
+            clear_suspended();
             return;
         }
     };
@@ -224,77 +372,98 @@ fn inject_and_dump_suspended(pid: u32, dll_path: &std::path::Path, output_path:
     // Helper to resume process on early return
     let resume_on_error = || {
         if let Err(e) = inject::resume_process(pid) {
-            eprintln!("{} Failed to resume process: {}", "[WARN]".yellow(), e);
+            log().warn(&format!("Failed to resume process: {}", e));
         } else {
-            println!("{} Process resumed", "[INFO]".blue());
+            log().info("Process resumed");
         }
+        clear_suspended();
     };
 
     // Set up IPC
-    println!("{} Setting up IPC...", "[INFO]".blue());
+    log().info("Setting up IPC...");
     let mut ipc = match IpcHost::create(pid) {
         Ok(ipc) => ipc,
         Err(e) => {
-            eprintln!("{} Failed to create IPC: {}", "[ERROR]".red(), e);
+            log().error(&format!("Failed to create IPC: {}", e));
             resume_on_error();
             return;
         }
     };
 
     // Inject DLL
-    println!("{} Injecting DLL...", "[INFO]".blue());
+    log().info("Injecting DLL...");
     let process = match inject::inject_dll(pid, dll_path) {
         Ok(handle) => handle,
         Err(e) => {
-            eprintln!("{} Injection failed: {}", "[ERROR]".red(), e);
+            log().error(&format!("Injection failed: {}", e));
             resume_on_error();
             return;
         }
     };
 
-    println!(
-        "{} DLL injected, waiting for ready signal...",
-        "[OK]".green()
-    );
+    log().success("DLL injected, waiting for ready signal...");
 
     // Wait for ready signal
     let mut ready = false;
     for _ in 0..500 {
+        // Read pending packets before the liveness check: a Fatal packet is
+        // raised while the target is still alive, the only moment a minidump can
+        // capture anything. Checking `is_alive()` first would lose that window.
+        if let Some(pkt) = ipc.try_read() {
+            match pkt.id() {
+                PacketId::Ready => {
+                    ready = true;
+                    break;
+                }
+                PacketId::Fatal => {
+                    log().error(&format!("Target reported fatal error: {}", pkt.message()));
+                    maybe_write_crash_dump(&process, pid);
+                    return; // Process is dying; nothing left to resume.
+                }
+                _ => display_packet(&pkt),
+            }
+        }
         if !process.is_alive() {
-            eprintln!(
-                "{} Target process crashed during initialization",
-                "[ERROR]".red()
-            );
+            log().error("Target process crashed during initialization");
             return; // Process is dead, no need to resume
         }
-        if let Some(pkt) = ipc.try_read()
-            && pkt.id() == PacketId::Ready
-        {
-            ready = true;
-            break;
-        }
 
-        std::thread::sleep(std::time::Duration::from_millis(10));
+        // Park on the IPC signal with a short ceiling instead of busy-polling.
+        use futures_lite::FutureExt;
+        futures_lite::future::block_on(async { ipc.readable().await }.or(async {
+            async_io::Timer::after(std::time::Duration::from_millis(10)).await;
+        }));
     }
 
     if !ready {
-        eprintln!("{} Timeout waiting for DLL ready signal", "[ERROR]".red());
+        log().error("Timeout waiting for DLL ready signal");
         resume_on_error();
         return;
     }
 
     // Start dump and run message loop
-    println!("{} Starting dump...\n", "[INFO]".blue());
+    log().info("Starting dump...\n");
 
-    ipc.start_dump();
+    send_config_and_start(&mut ipc);
 
-    let dump_path = run_message_loop(&mut ipc, &process);
+    // Reconstruct streamed files directly into the chosen output directory on
+    // the host, so the dump needs no writable space inside the sandbox.
+    let dest = resolve_output_dir(output_path);
+    let dump_path = run_message_loop(&mut ipc, &process, pid, Some(&dest));
 
-    // If custom output path was specified, copy files from TempState to destination
-    if let (Some(output), Some(source)) = (output_path, dump_path) {
-        copy_to_output(&source, output);
+    // Fold in any files the payload staged locally via the large-file fallback.
+    if let Some(source) = dump_path {
+        copy_to_output(&source, &dest.to_string_lossy());
     }
 
+    // Optionally pack the reconstructed tree into a single compressed archive.
+    if let Some(Some(archive)) = ARCHIVE_PATH.get() {
+        archive_output(&dest, archive);
+    }
+
+    // The dump is done; the target no longer needs the suspend guard.
+    clear_suspended();
+
     println!();
 }
 
@@ -302,92 +471,301 @@ fn inject_and_dump(pid: u32, dll_path: &std::path::Path, output_path: Option<&st
     // Check if process is 32-bit (we only support 64-bit)
     match inject::is_process_32bit(pid) {
         Ok(true) => {
-            eprintln!(
-                "{} Target process is 32-bit. This tool only supports 64-bit processes.",
-                "[ERROR]".red()
-            );
-            eprintln!(
-                "{} You need a 32-bit build of the injector and payload DLL.",
-                "[INFO]".blue()
-            );
+            log().error("Target process is 32-bit. This tool only supports 64-bit processes.");
+            log().info("You need a 32-bit build of the injector and payload DLL.");
             return;
         }
         Ok(false) => {} // 64-bit, continue
         Err(e) => {
-            eprintln!(
-                "{} Warning: Could not determine process architecture: {}",
-                "[WARN]".yellow(),
-                e
-            );
+            log().warn(&format!("Warning: Could not determine process architecture: {}", e));
             // Continue anyway, injection will fail if architecture mismatch
         }
     }
 
     // Set up IPC
-    println!("{} Setting up IPC...", "[INFO]".blue());
+    log().info("Setting up IPC...");
     let mut ipc = match IpcHost::create(pid) {
         Ok(ipc) => ipc,
         Err(e) => {
-            eprintln!("{} Failed to create IPC: {}", "[ERROR]".red(), e);
+            log().error(&format!("Failed to create IPC: {}", e));
             return;
         }
     };
 
     // Inject DLL
-    println!("{} Injecting DLL...", "[INFO]".blue());
+    log().info("Injecting DLL...");
     let process = match inject::inject_dll(pid, dll_path) {
         Ok(handle) => handle,
         Err(e) => {
-            eprintln!("{} Injection failed: {}", "[ERROR]".red(), e);
+            log().error(&format!("Injection failed: {}", e));
             return;
         }
     };
 
-    println!(
-        "{} DLL injected, waiting for ready signal...",
-        "[OK]".green()
-    );
+    log().success("DLL injected, waiting for ready signal...");
 
     // Wait for ready signal
     let mut ready = false;
     for _ in 0..500 {
+        // Read pending packets before the liveness check: a Fatal packet is
+        // raised while the target is still alive, the only moment a minidump can
+        // capture anything. Checking `is_alive()` first would lose that window.
+        if let Some(pkt) = ipc.try_read() {
+            match pkt.id() {
+                PacketId::Ready => {
+                    ready = true;
+                    break;
+                }
+                PacketId::Fatal => {
+                    log().error(&format!("Target reported fatal error: {}", pkt.message()));
+                    maybe_write_crash_dump(&process, pid);
+                    return;
+                }
+                _ => display_packet(&pkt),
+            }
+        }
         if !process.is_alive() {
-            eprintln!(
-                "{} Target process crashed during initialization",
-                "[ERROR]".red()
-            );
+            log().error("Target process crashed during initialization");
             return;
         }
-        if let Some(pkt) = ipc.try_read()
-            && pkt.id() == PacketId::Ready
-        {
-            ready = true;
-            break;
-        }
 
-        std::thread::sleep(std::time::Duration::from_millis(10));
+        // Park on the IPC signal with a short ceiling instead of busy-polling.
+        use futures_lite::FutureExt;
+        futures_lite::future::block_on(async { ipc.readable().await }.or(async {
+            async_io::Timer::after(std::time::Duration::from_millis(10)).await;
+        }));
     }
 
     if !ready {
-        eprintln!("{} Timeout waiting for DLL ready signal", "[ERROR]".red());
+        log().error("Timeout waiting for DLL ready signal");
         return;
     }
 
     // Start dump and run message loop
-    println!("{} Starting dump...\n", "[INFO]".blue());
+    log().info("Starting dump...\n");
 
-    ipc.start_dump();
+    send_config_and_start(&mut ipc);
+
+    // Reconstruct streamed files directly into the chosen output directory on
+    // the host, so the dump needs no writable space inside the sandbox.
+    let dest = resolve_output_dir(output_path);
+    let dump_path = run_message_loop(&mut ipc, &process, pid, Some(&dest));
+
+    // Fold in any files the payload staged locally via the large-file fallback.
+    if let Some(source) = dump_path {
+        copy_to_output(&source, &dest.to_string_lossy());
+    }
+
+    // Optionally pack the reconstructed tree into a single compressed archive.
+    if let Some(Some(archive)) = ARCHIVE_PATH.get() {
+        archive_output(&dest, archive);
+    }
+
+    // The dump is done; the target no longer needs the suspend guard.
+    clear_suspended();
+
+    println!();
+}
+
+/// Inject into every UWP process sharing the target's package family and merge
+/// their dumps under one output root, one subfolder per PID.
+fn inject_and_dump_tree(
+    target: &process::ProcessInfo,
+    dll_path: &std::path::Path,
+    output_path: Option<&str>,
+) {
+    // Discover the children and siblings that map the same package family.
+    let members = match process::family_tree(target) {
+        Ok(m) => m,
+        Err(e) => {
+            log().error(&format!("Failed to enumerate process tree: {}", e));
+            return;
+        }
+    };
+
+    log().info(&format!("Dumping {} processes in family: {}", members.len(), target.family_name));
+
+    let root = resolve_output_dir(output_path);
+
+    // Set up IPC + inject into each member before starting any dump, so the
+    // host can multiplex packets from all of them afterwards.
+    let mut targets = Vec::new();
+    for member in members {
+        log().info(&format!("Preparing PID {} ({})", member.pid, member.name));
+
+        let mut ipc = match IpcHost::create(member.pid) {
+            Ok(ipc) => ipc,
+            Err(e) => {
+                log().warn(&format!("Skipping PID {}: IPC setup failed: {}", member.pid, e));
+                continue;
+            }
+        };
+
+        let process = match inject::inject_dll(member.pid, dll_path) {
+            Ok(p) => p,
+            Err(e) => {
+                log().warn(&format!("Skipping PID {}: injection failed: {}", member.pid, e));
+                continue;
+            }
+        };
+
+        // Wait for this member's ready signal before moving on.
+        let mut ready = false;
+        for _ in 0..500 {
+            if !process.is_alive() {
+                break;
+            }
+            if let Some(pkt) = ipc.try_read()
+                && pkt.id() == PacketId::Ready
+            {
+                ready = true;
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        if !ready {
+            log().warn(&format!("Skipping PID {}: no ready signal", member.pid));
+            continue;
+        }
 
-    let dump_path = run_message_loop(&mut ipc, &process);
+        send_config_and_start(&mut ipc);
+        let writer = StreamWriter::new(&root.join(member.pid.to_string()));
+        targets.push(TreeTarget {
+            pid: member.pid,
+            ipc,
+            process,
+            writer,
+            finished: false,
+        });
+    }
 
-    // If custom output path was specified, copy files from TempState to destination
-    if let (Some(output), Some(source)) = (output_path, dump_path) {
-        copy_to_output(&source, output);
+    if targets.is_empty() {
+        log().error("No processes could be dumped");
+        return;
     }
 
+    log().info("Starting tree dump...\n");
+    run_tree_message_loop(&mut targets);
+
+    // Optionally pack the merged per-PID tree into a single compressed archive.
+    if let Some(Some(archive)) = ARCHIVE_PATH.get() {
+        archive_output(&root, archive);
+    }
     println!();
 }
 
+/// One member of a process-tree dump.
+struct TreeTarget {
+    pid: u32,
+    ipc: IpcHost,
+    process: inject::ProcessHandle,
+    writer: StreamWriter,
+    finished: bool,
+}
+
+/// Multiplexed message loop over several `IpcHost` instances.
+///
+/// Services every target each tick, reconstructing streamed files into each
+/// target's subfolder and reporting combined progress across the tree.
+fn run_tree_message_loop(targets: &mut [TreeTarget]) {
+    loop {
+        let mut all_done = true;
+        let mut combined = (0u32, 0u32);
+
+        for target in targets.iter_mut() {
+            if target.finished {
+                continue;
+            }
+
+            // Drain packets before the liveness check so a Fatal packet - raised
+            // while the target is still alive - is captured before its address
+            // space is gone. A post-mortem `is_alive()` dump has nothing left.
+            while let Some(pkt) = target.ipc.try_read() {
+                match pkt.id() {
+                    PacketId::Complete => {
+                        clear_progress_line();
+                        // Complete carries the dump path; the count rides the
+                        // per-PID Success line handled by display_packet below.
+                        log().log(logger::Severity::Complete, &format!("PID {}: {}", target.pid, pkt.message()), None);
+                        target.finished = true;
+                    }
+                    PacketId::Fatal => {
+                        clear_progress_line();
+                        log().log(logger::Severity::Fatal, &format!("PID {}: {}", target.pid, pkt.message()), None);
+                        maybe_write_crash_dump(&target.process, target.pid);
+                        target.finished = true;
+                    }
+                    PacketId::FileHeader => target.writer.on_header(pkt.message()),
+                    PacketId::FileChunk => target.writer.on_chunk(pkt.payload()),
+                    PacketId::FileEnd => target.writer.on_end(),
+                    _ => display_packet(&pkt),
+                }
+            }
+
+            if !target.finished && !target.process.is_alive() {
+                clear_progress_line();
+                log().warn(&format!("PID {} crashed or terminated", target.pid));
+                target.finished = true;
+                continue;
+            }
+
+            target.ipc.check_and_ack_sync();
+            if target.ipc.is_finished() {
+                target.finished = true;
+            }
+
+            if !target.finished {
+                all_done = false;
+            }
+
+            let (cur, tot) = target.ipc.get_progress();
+            combined.0 += cur;
+            combined.1 += tot;
+        }
+
+        if combined != (0, 0) {
+            display_progress(combined.0, combined.1);
+        }
+
+        if all_done {
+            clear_progress_line();
+            return;
+        }
+
+        // Park until any live target has a packet ready or exits, or the next
+        // progress tick fires, rather than spinning on a fixed sleep. The
+        // per-target `readable()`/`wait_exit()` futures live on IpcHost and
+        // ProcessHandle in the shared and inject modules; we race the whole set
+        // so a signal from any target wakes the loop immediately.
+        use futures_lite::FutureExt;
+        let mut wait: std::pin::Pin<Box<dyn std::future::Future<Output = ()> + '_>> =
+            Box::pin(async {
+                async_io::Timer::after(PROGRESS_TICK).await;
+            });
+        for target in targets.iter().filter(|t| !t.finished) {
+            wait = Box::pin(
+                wait.or(async { target.ipc.readable().await })
+                    .or(async { target.process.wait_exit().await }),
+            );
+        }
+        futures_lite::future::block_on(wait);
+    }
+}
+
+/// Resolve the host-side output directory for a dump.
+///
+/// Uses the user-supplied `--output` path when present, otherwise defaults to a
+/// `DUMP` directory inside the current working directory.
+fn resolve_output_dir(output_path: Option<&str>) -> PathBuf {
+    match output_path {
+        Some(p) => PathBuf::from(p),
+        None => std::env::current_dir()
+            .unwrap_or_else(|_| PathBuf::from("."))
+            .join("DUMP"),
+    }
+}
+
 /// Copy dumped files from TempState to custom output directory
 fn copy_to_output(source: &str, dest: &str) {
     use rayon::prelude::*;
@@ -398,19 +776,31 @@ fn copy_to_output(source: &str, dest: &str) {
     let dest_path = Path::new(dest);
 
     if !source_path.exists() {
-        eprintln!("{} Source path does not exist: {}", "[ERROR]".red(), source);
+        // Expected in streaming mode when nothing used the local-copy fallback.
         return;
     }
 
-    println!("\n{} Copying files to custom output...", "[INFO]".blue());
+    log().info("\nCopying files to custom output...");
 
     // Create destination directory
     if let Err(e) = std::fs::create_dir_all(dest_path) {
-        eprintln!(
-            "{} Failed to create output directory: {}",
-            "[ERROR]".red(),
-            e
-        );
+        log().error(&format!("Failed to create output directory: {}", e));
+        return;
+    }
+
+    // Archive mode returns a single `DUMP.zip` rather than a tree; walking a
+    // lone file yields an empty relative path, so retrieve it by name here
+    // instead of letting the directory copy below fail.
+    if source_path.is_file() {
+        let name = source_path
+            .file_name()
+            .map(Path::new)
+            .unwrap_or_else(|| Path::new("DUMP.zip"));
+        let dst = dest_path.join(name);
+        match std::fs::copy(source_path, &dst) {
+            Ok(_) => log().success(&format!("Copied {} to {}", name.display(), dest)),
+            Err(e) => log().error(&format!("Failed to copy archive: {}", e)),
+        }
         return;
     }
 
@@ -437,11 +827,7 @@ fn copy_to_output(source: &str, dest: &str) {
         .collect();
 
     let file_count = files.len();
-    println!(
-        "{} Copying {} files (parallel)...",
-        "[INFO]".blue(),
-        file_count
-    );
+    log().info(&format!("Copying {} files (parallel)...", file_count));
 
     use std::sync::Arc;
 
@@ -453,34 +839,7 @@ fn copy_to_output(source: &str, dest: &str) {
     let total = file_count as u32;
     let progress_handle = std::thread::spawn({
         let processed = Arc::clone(&processed);
-        move || {
-            let mut last_percent = 0;
-            loop {
-                let current = processed.load(Ordering::Relaxed);
-                let percent = if total > 0 {
-                    (current * 100 / total) as usize
-                } else {
-                    0
-                };
-                if percent != last_percent || current == total {
-                    print!(
-                        "\r\x1b[K{} [{}{}] {}% ({}/{})",
-                        "[COPY]".cyan(),
-                        "█".repeat(percent * 40 / 100),
-                        "░".repeat(40 - percent * 40 / 100),
-                        percent,
-                        current,
-                        total
-                    );
-                    let _ = io::stdout().flush();
-                    last_percent = percent;
-                }
-                if current >= total {
-                    break;
-                }
-                std::thread::sleep(std::time::Duration::from_millis(50));
-            }
-        }
+        move || render_copy_progress(&processed, total)
     });
 
     // Copy files in parallel
@@ -507,13 +866,201 @@ fn copy_to_output(source: &str, dest: &str) {
     let final_errors = errors.load(Ordering::Relaxed);
 
     println!();
-    println!(
-        "{} Copied {} files ({} errors) to {}",
-        "[OK]".green(),
-        final_copied,
-        final_errors,
-        dest
-    );
+    log().success(&format!("Copied {} files ({} errors) to {}", final_copied, final_errors, dest));
+}
+
+/// Render the `[COPY] [████░░] %` progress bar until `processed` reaches `total`.
+///
+/// Shared by [`copy_to_output`] and [`archive_output`] so both show identical
+/// feedback while work happens on the rayon pool.
+fn render_copy_progress(processed: &std::sync::atomic::AtomicU32, total: u32) {
+    let mut last_percent = 0;
+    loop {
+        let current = processed.load(Ordering::Relaxed);
+        let percent = if total > 0 {
+            (current * 100 / total) as usize
+        } else {
+            0
+        };
+        if percent != last_percent || current == total {
+            print!(
+                "\r\x1b[K{} [{}{}] {}% ({}/{})",
+                "[COPY]".cyan(),
+                "█".repeat(percent * 40 / 100),
+                "░".repeat(40 - percent * 40 / 100),
+                percent,
+                current,
+                total
+            );
+            let _ = io::stdout().flush();
+            last_percent = percent;
+        }
+        if current >= total {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+}
+
+/// Pack a dumped directory tree into a single compressed archive.
+///
+/// A single writer thread serializes the entries so the central directory stays
+/// consistent, but it streams each file straight from disk into the archive
+/// rather than buffering whole files in memory first - so a large dump no longer
+/// pins dozens of multi-megabyte `Vec`s in RAM. `.tar.zst` is chosen by
+/// extension and hands the compression to zstd's own worker threads so deflation
+/// runs in parallel with the serialization; everything else writes a ZIP.
+/// Reuses the same atomic counters and progress-bar thread as [`copy_to_output`].
+fn archive_output(source: &std::path::Path, archive: &std::path::Path) {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicU32;
+
+    if !source.exists() {
+        return;
+    }
+
+    log().info(&format!("\nPacking dump into archive: {}", archive.display()));
+
+    if let Some(parent) = archive.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+
+    // (relative archive name, absolute source path) for every file to pack. The
+    // writer opens each source itself, so nothing holds a file's bytes beyond
+    // the in-flight copy buffer.
+    let entries: Vec<(String, PathBuf)> = walkdir::WalkDir::new(source)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| {
+            let relative = e.path().strip_prefix(source).unwrap_or(e.path());
+            // Archive entries always use forward slashes.
+            let name = relative.to_string_lossy().replace('\\', "/");
+            (name, e.path().to_path_buf())
+        })
+        .collect();
+
+    let total = entries.len() as u32;
+    let processed = Arc::new(AtomicU32::new(0));
+    let errors = Arc::new(AtomicU32::new(0));
+
+    let progress_handle = std::thread::spawn({
+        let processed = Arc::clone(&processed);
+        move || render_copy_progress(&processed, total)
+    });
+
+    if is_tar_zst(archive) {
+        write_tar_zst(archive, &entries, &processed, &errors);
+    } else {
+        write_zip(archive, &entries, &processed, &errors);
+    }
+
+    let _ = progress_handle.join();
+
+    println!();
+    let error_count = errors.load(Ordering::Relaxed);
+    log().success(&format!("Wrote archive {} ({} errors)", archive.display(), error_count));
+
+    // The archive is meant to replace the loose dump, not sit beside it
+    // (doubling disk usage). Remove the reconstructed tree once every file made
+    // it in; on any error keep it so nothing is lost.
+    if error_count == 0 {
+        match std::fs::remove_dir_all(source) {
+            Ok(()) => log().info(&format!("Removed loose dump tree: {}", source.display())),
+            Err(e) => log().warn(&format!("Could not remove loose dump tree {}: {}", source.display(), e)),
+        }
+    } else {
+        log().warn("Keeping loose dump tree because the archive had errors");
+    }
+}
+
+/// Number of zstd worker threads to use for the `.tar.zst` path, derived from
+/// the host's parallelism (clamped to at least one).
+fn zstd_workers() -> u32 {
+    std::thread::available_parallelism()
+        .map(|n| n.get() as u32)
+        .unwrap_or(1)
+}
+
+/// Whether the archive path names a `.tar.zst` (vs. a ZIP).
+fn is_tar_zst(path: &std::path::Path) -> bool {
+    path.to_string_lossy().to_lowercase().ends_with(".tar.zst")
+}
+
+/// Serialize entries into a ZIP archive, streaming each file from disk.
+fn write_zip(
+    path: &std::path::Path,
+    entries: &[(String, PathBuf)],
+    processed: &std::sync::atomic::AtomicU32,
+    errors: &std::sync::atomic::AtomicU32,
+) {
+    use zip::write::SimpleFileOptions;
+
+    let file = match std::fs::File::create(path) {
+        Ok(f) => f,
+        Err(_) => {
+            errors.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+    };
+    let mut zip = zip::ZipWriter::new(std::io::BufWriter::new(file));
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+
+    for (name, src) in entries {
+        let result = std::fs::File::open(src).and_then(|f| {
+            zip.start_file(name, options)
+                .map_err(std::io::Error::other)?;
+            let mut reader = std::io::BufReader::with_capacity(64 * 1024, f);
+            std::io::copy(&mut reader, &mut zip)?;
+            Ok(())
+        });
+        if result.is_err() {
+            errors.fetch_add(1, Ordering::Relaxed);
+        }
+        processed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let _ = zip.finish();
+}
+
+/// Serialize entries into a zstd-compressed tarball, streaming each file from
+/// disk and letting zstd compress on its own worker threads so deflation runs
+/// in parallel with the tar serialization.
+fn write_tar_zst(
+    path: &std::path::Path,
+    entries: &[(String, PathBuf)],
+    processed: &std::sync::atomic::AtomicU32,
+    errors: &std::sync::atomic::AtomicU32,
+) {
+    let file = match std::fs::File::create(path) {
+        Ok(f) => f,
+        Err(_) => {
+            errors.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+    };
+    let mut encoder = match zstd::Encoder::new(std::io::BufWriter::new(file), 3) {
+        Ok(e) => e,
+        Err(_) => {
+            errors.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+    };
+    // Fan the compression out across zstd's worker pool; the single tar builder
+    // still feeds it in order, so the archive stays deterministic.
+    let _ = encoder.multithread(zstd_workers());
+    let mut tar = tar::Builder::new(encoder.auto_finish());
+
+    for (name, src) in entries {
+        if tar.append_path_with_name(src, name).is_err() {
+            errors.fetch_add(1, Ordering::Relaxed);
+        }
+        processed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let _ = tar.finish();
 }
 
 fn find_process(pid: Option<u32>, name: Option<&str>) -> Option<process::ProcessInfo> {
@@ -532,27 +1079,23 @@ fn find_process(pid: Option<u32>, name: Option<&str>) -> Option<process::Process
 }
 
 fn select_process_interactive() -> Option<process::ProcessInfo> {
-    println!("{} Scanning for UWP processes...", "[INFO]".blue());
+    log().info("Scanning for UWP processes...");
     let processes = match process::list_uwp_processes() {
         Ok(p) => p,
         Err(e) => {
-            eprintln!("{} Failed to list processes: {}", "[ERROR]".red(), e);
+            log().error(&format!("Failed to list processes: {}", e));
             return None;
         }
     };
 
     if processes.is_empty() {
-        println!("{} No UWP processes found.", "[WARN]".yellow());
+        log().warn("No UWP processes found.");
 
         return None;
     }
 
     // Display process list
-    println!(
-        "\n{} Found {} UWP processes:\n",
-        "[OK]".green(),
-        processes.len()
-    );
+    log().success(&format!("\nFound {} UWP processes:\n", processes.len()));
     for (i, proc) in processes.iter().enumerate() {
         println!("  [{}] {} (PID: {})", i + 1, proc.name.cyan(), proc.pid);
     }
@@ -569,7 +1112,7 @@ fn select_process_interactive() -> Option<process::ProcessInfo> {
     let selection: usize = match input.trim().parse() {
         Ok(n) => n,
         Err(_) => {
-            eprintln!("{} Invalid input", "[ERROR]".red());
+            log().error("Invalid input");
             return None;
         }
     };
@@ -590,36 +1133,128 @@ fn clear_progress_line() {
     }
 }
 
-/// Main message loop - polls progress and displays packets
-/// Returns the dump path from the Complete packet if successful
-fn run_message_loop(ipc: &mut IpcHost, process: &inject::ProcessHandle) -> Option<String> {
-    let mut last_progress = (0u32, 0u32);
+/// Reconstructs a directory tree from streamed file-data packets on the host.
+///
+/// Files arrive as a header packet (relative path + length), a run of chunk
+/// packets, then an end packet; this writer serializes them to `root`.
+struct StreamWriter {
+    root: PathBuf,
+    current: Option<std::fs::File>,
+}
 
-    loop {
-        // Check if target process crashed
-        if !process.is_alive() {
-            clear_progress_line();
-            eprintln!(
-                "\n{} Target process crashed or was terminated",
-                "[ERROR]".red()
-            );
-            return None;
+impl StreamWriter {
+    fn new(root: &std::path::Path) -> Self {
+        let _ = std::fs::create_dir_all(root);
+        Self {
+            root: root.to_path_buf(),
+            current: None,
+        }
+    }
+
+    fn on_header(&mut self, relative: &str) {
+        // The payload frames each file with the relative path from the package
+        // root, but we never trust it blindly: an absolute path, drive prefix or
+        // `..` component would let a crafted header write outside `root`. Keep
+        // only normal path components and rebuild the destination under `root`.
+        let mut dest = self.root.clone();
+        for component in std::path::Path::new(relative).components() {
+            if let std::path::Component::Normal(part) = component {
+                dest.push(part);
+            }
+        }
+
+        // A header that sanitized down to nothing (just separators or `..`) has
+        // no valid destination; drop the frame rather than writing into `root`.
+        if dest == self.root {
+            self.current = None;
+            return;
+        }
+
+        if let Some(parent) = dest.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        self.current = std::fs::File::create(&dest).ok();
+    }
+
+    fn on_chunk(&mut self, data: &[u8]) {
+        if let Some(file) = self.current.as_mut() {
+            let _ = file.write_all(data);
         }
+    }
+
+    fn on_end(&mut self) {
+        self.current = None;
+    }
+}
+
+/// How often the message loop wakes to refresh progress when no packet arrives.
+const PROGRESS_TICK: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Main event-driven message loop - displays packets and progress.
+/// Returns the dump path from the Complete packet if successful.
+///
+/// Instead of busy-polling with a fixed sleep, each iteration parks on a
+/// `select!`-style race between (a) the shared-memory signal `IpcHost` raises
+/// when a packet is ready, (b) a periodic progress tick, and (c) the target's
+/// exit. This removes the per-packet latency of the old 10 ms poll and lets the
+/// crash-detection branch compose cleanly alongside the packet and tick paths.
+///
+/// When `stream_dest` is set, incoming file-data packets are reconstructed into
+/// that directory on the host instead of being staged inside the sandbox.
+fn run_message_loop(
+    ipc: &mut IpcHost,
+    process: &inject::ProcessHandle,
+    pid: u32,
+    stream_dest: Option<&std::path::Path>,
+) -> Option<String> {
+    let mut last_progress = (0u32, 0u32);
+    let mut writer = stream_dest.map(StreamWriter::new);
 
-        // Process packets first (messages should appear before progress)
+    loop {
+        // Drain packets before testing liveness. A Fatal packet is the payload's
+        // own signal that it is about to die, sent while the target still has an
+        // address space to dump; if we checked `is_alive()` first we would race
+        // the exit and only ever reach the post-mortem branch below, where
+        // MiniDumpWriteDump has nothing left to capture.
         while let Some(pkt) = ipc.try_read() {
-            display_packet(&pkt);
             match pkt.id() {
                 PacketId::Complete => {
+                    display_packet(&pkt);
                     return Some(pkt.message().to_string());
                 }
                 PacketId::Fatal => {
+                    display_packet(&pkt);
+                    maybe_write_crash_dump(process, pid);
                     return None;
                 }
-                _ => {}
+                PacketId::FileHeader => {
+                    if let Some(w) = writer.as_mut() {
+                        w.on_header(pkt.message());
+                    }
+                }
+                PacketId::FileChunk => {
+                    if let Some(w) = writer.as_mut() {
+                        w.on_chunk(pkt.payload());
+                    }
+                }
+                PacketId::FileEnd => {
+                    if let Some(w) = writer.as_mut() {
+                        w.on_end();
+                    }
+                }
+                _ => display_packet(&pkt),
             }
         }
 
+        // With no Fatal packet pending, a dead target means it crashed without
+        // announcing it. Its address space is already gone, so we can only
+        // report the termination - there is nothing left to dump here.
+        if !process.is_alive() {
+            clear_progress_line();
+            log().error("\nTarget process crashed or was terminated");
+            return None;
+        }
+
         // Poll and display progress
         let progress = ipc.get_progress();
         if progress != last_progress {
@@ -641,7 +1276,20 @@ fn run_message_loop(ipc: &mut IpcHost, process: &inject::ProcessHandle) -> Optio
             return None;
         }
 
-        std::thread::sleep(std::time::Duration::from_millis(10));
+        // Park until a packet signal, the next progress tick, or target exit,
+        // rather than spinning on a fixed sleep.
+        use futures_lite::FutureExt;
+        futures_lite::future::block_on(
+            async {
+                ipc.readable().await;
+            }
+            .or(async {
+                async_io::Timer::after(PROGRESS_TICK).await;
+            })
+            .or(async {
+                process.wait_exit().await;
+            }),
+        );
     }
 }
 
@@ -661,8 +1309,10 @@ fn display_progress(current: u32, total: u32) {
             current
         );
     } else {
-        // Known total - show progress bar
-        let percent = (current * 100) / total;
+        // Known total - show progress bar. Compute in u64: progress is reported
+        // in KiB, so `current * 100` overflows u32 once a dump passes ~41 GiB -
+        // routine for the multi-gigabyte UWP titles this path targets.
+        let percent = (current as u64 * 100 / total as u64) as u32;
         let bar_width = 40;
         let filled = (percent as usize * bar_width) / 100;
         let bar = format!("{}{}", "█".repeat(filled), "░".repeat(bar_width - filled));
@@ -679,26 +1329,36 @@ fn display_progress(current: u32, total: u32) {
     let _ = io::stdout().flush();
 }
 
-/// Display a packet from the DLL
+/// Extract the dumped-file count from a completion summary line.
+///
+/// The payload emits the summary as a `Success` log packet reading
+/// `"<verb> <n> files (<e> errors) in <t>s"`, where `<verb>` is `Dumped`,
+/// `Streamed` or `Archived` (see `dumper.rs`). Matching the verb prefix keeps
+/// scan-time lines like `"Found N files to dump"` from being read as a count.
+/// Returns `None` for any other message, leaving `file_count` unset.
+fn completion_file_count(message: &str) -> Option<u64> {
+    let rest = ["Dumped ", "Streamed ", "Archived "]
+        .iter()
+        .find_map(|prefix| message.strip_prefix(prefix))?;
+    rest.split_whitespace().next()?.parse().ok()
+}
+
+/// Display a packet from the DLL through the central logger
 fn display_packet(pkt: &Packet) {
     match pkt.id() {
         PacketId::Log => {
-            clear_progress_line();
-            match pkt.log_level() {
-                Some(LogLevel::Info) => println!("{} {}", "[INFO]".blue(), pkt.message()),
-                Some(LogLevel::Success) => println!("{} {}", "[OK]".green(), pkt.message()),
-                Some(LogLevel::Warning) => println!("{} {}", "[WARN]".yellow(), pkt.message()),
-                Some(LogLevel::Error) => println!("{} {}", "[ERROR]".red(), pkt.message()),
-                None => {}
+            if let Some(level) = pkt.log_level() {
+                // The "Dumped/Streamed/Archived N files" line arrives as a
+                // Success log packet; carry its count into the JSON record.
+                log().log_level_with_count(level, pkt.message(), completion_file_count(pkt.message()));
             }
         }
         PacketId::Complete => {
-            clear_progress_line();
-            println!("{} {}", "[DONE]".green().bold(), pkt.message());
+            // The Complete packet carries the dump path, not a file count.
+            log().log(logger::Severity::Complete, pkt.message(), None);
         }
         PacketId::Fatal => {
-            clear_progress_line();
-            println!("{} {}", "[FATAL]".red().bold(), pkt.message());
+            log().log(logger::Severity::Fatal, pkt.message(), None);
         }
         _ => {}
     }
@@ -1,11 +1,13 @@
 //! UWP Package enumeration and launching
 
 use std::process::Command;
+use windows::ApplicationModel::Package;
+use windows::Management::Deployment::PackageManager;
 use windows::Win32::System::Com::{
     CLSCTX_LOCAL_SERVER, COINIT_MULTITHREADED, CoCreateInstance, CoInitializeEx,
 };
-use windows::Win32::UI::Shell::{ACTIVATEOPTIONS, IApplicationActivationManager};
-use windows::core::{GUID, HRESULT};
+use windows::Win32::UI::Shell::{ACTIVATEOPTIONS, IApplicationActivationManager, SHLoadIndirectString};
+use windows::core::{GUID, HRESULT, HSTRING, PCWSTR};
 
 /// Error type for package operations
 #[derive(Debug)]
@@ -54,7 +56,119 @@ pub struct InstalledPackage {
 }
 
 /// List all installed UWP packages
+///
+/// Uses the native WinRT `PackageManager` backend by default, which works even
+/// in locked-down environments with no `powershell.exe`. Falls back to the
+/// PowerShell enumeration if the WinRT path is unavailable.
 pub fn list_packages() -> Result<Vec<InstalledPackage>, PackageError> {
+    match list_packages_winrt() {
+        Ok(packages) => Ok(packages),
+        Err(e) => {
+            // WinRT is unavailable (very old OS, restricted COM) - fall back to
+            // the legacy PowerShell enumeration so we still degrade gracefully.
+            eprintln!("WinRT enumeration failed ({e}); falling back to PowerShell");
+            list_packages_powershell()
+        }
+    }
+}
+
+/// Enumerate installed packages via `Windows.Management.Deployment.PackageManager`.
+fn list_packages_winrt() -> Result<Vec<InstalledPackage>, PackageError> {
+    let manager = PackageManager::new()?;
+
+    // FindPackagesForUser("") enumerates every package installed for the
+    // current user without requiring elevation.
+    let packages = manager.FindPackagesForUser(&HSTRING::new())?;
+
+    let mut result = Vec::new();
+    for package in packages {
+        match package_to_installed(&package) {
+            Ok(pkg) => result.push(pkg),
+            // Skip individual packages we can't read rather than failing the
+            // whole enumeration (framework packages, in-progress installs, ...).
+            Err(_) => continue,
+        }
+    }
+
+    Ok(result)
+}
+
+/// Convert a WinRT `Package` into our `InstalledPackage`.
+fn package_to_installed(package: &Package) -> Result<InstalledPackage, PackageError> {
+    let id = package.Id()?;
+    let name = id.Name()?.to_string();
+    let family_name = id.FamilyName()?.to_string();
+    let full_name = id.FullName()?.to_string();
+
+    // `DisplayName()` already returns a resolved name for most packages; only the
+    // ones that hand back a raw `ms-resource:` reference need resolving against
+    // the package's resource map, with a fall back to the raw name.
+    let raw_display = package
+        .DisplayName()
+        .map(|s| s.to_string())
+        .unwrap_or_default();
+    let display_name = if raw_display.is_empty() || raw_display.starts_with("ms-resource:") {
+        resolve_indirect_string(&full_name, &raw_display).unwrap_or_else(|| name.clone())
+    } else {
+        raw_display
+    };
+
+    // Resolve the first application's id from the package's app list entries,
+    // falling back to "App" (correct for most packages) when the package
+    // exposes no launchable entry.
+    let app_id = resolve_app_id(package).unwrap_or_else(|| "App".to_string());
+
+    Ok(InstalledPackage {
+        name,
+        display_name,
+        family_name,
+        app_id,
+    })
+}
+
+/// Resolve the first application id of a package from its app list entries.
+///
+/// Each entry's AppUserModelId is `FamilyName!ApplicationId`; we take the id
+/// after the `!`. Returns `None` when the package has no launchable entry.
+fn resolve_app_id(package: &Package) -> Option<String> {
+    let entries = package.GetAppListEntries().ok()?;
+    let first = entries.GetAt(0).ok()?;
+    let aumid = first.AppUserModelId().ok()?.to_string();
+    aumid
+        .split_once('!')
+        .map(|(_, app_id)| app_id.to_string())
+        .filter(|app_id| !app_id.is_empty())
+}
+
+/// Resolve an `ms-resource:` indirect string via `SHLoadIndirectString`.
+///
+/// A bare `ms-resource:` reference carries no package context, so
+/// `SHLoadIndirectString` can't find the resource map; it has to be wrapped as
+/// `@{PackageFullName?ms-resource://...}` so the loader resolves it against the
+/// owning package. Returns `None` if the reference is empty or can't be
+/// resolved.
+fn resolve_indirect_string(full_name: &str, resource: &str) -> Option<String> {
+    if resource.is_empty() {
+        return None;
+    }
+
+    let source = format!("@{{{}?{}}}", full_name, resource);
+    let source_wide: Vec<u16> = source.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut buffer = [0u16; 1024];
+
+    unsafe {
+        SHLoadIndirectString(PCWSTR(source_wide.as_ptr()), &mut buffer, None).ok()?;
+    }
+
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    if len == 0 {
+        return None;
+    }
+    Some(String::from_utf16_lossy(&buffer[..len]))
+}
+
+/// Legacy PowerShell-based package enumeration (fallback path).
+fn list_packages_powershell() -> Result<Vec<InstalledPackage>, PackageError> {
     // Use PowerShell to enumerate packages with display names and app IDs from manifest
     let output = Command::new("powershell")
         .args([
@@ -0,0 +1,116 @@
+//! UWP process enumeration and package-family grouping
+
+use windows::Win32::Foundation::{
+    CloseHandle, ERROR_INSUFFICIENT_BUFFER, ERROR_SUCCESS, HANDLE,
+};
+use windows::Win32::Storage::Packaging::Appx::GetPackageFamilyName;
+use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, PROCESSENTRY32W, Process32FirstW, Process32NextW, TH32CS_SNAPPROCESS,
+};
+use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+use windows::core::PWSTR;
+
+/// A running UWP process and where it sits in its package family.
+#[derive(Debug, Clone)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub name: String,
+    /// PID of the process that created this one, used to group family members
+    /// by parent.
+    pub parent_pid: u32,
+    /// Package family name (e.g. `Microsoft.HoganThreshold_8wekyb3d8bbwe`).
+    pub family_name: String,
+}
+
+/// Enumerate every running UWP process on the system.
+///
+/// A process counts as UWP when `GetPackageFamilyName` resolves a family for it;
+/// unpackaged processes return `APPMODEL_ERROR_NO_PACKAGE` and are skipped.
+pub fn list_uwp_processes() -> windows::core::Result<Vec<ProcessInfo>> {
+    let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0)? };
+
+    let mut entry = PROCESSENTRY32W {
+        dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+        ..Default::default()
+    };
+
+    let mut processes = Vec::new();
+
+    unsafe {
+        if Process32FirstW(snapshot, &mut entry).is_ok() {
+            loop {
+                if let Some(family_name) = package_family_name(entry.th32ProcessID) {
+                    processes.push(ProcessInfo {
+                        pid: entry.th32ProcessID,
+                        name: exe_name(&entry.szExeFile),
+                        parent_pid: entry.th32ParentProcessID,
+                        family_name,
+                    });
+                }
+                if Process32NextW(snapshot, &mut entry).is_err() {
+                    break;
+                }
+            }
+        }
+        let _ = CloseHandle(snapshot);
+    }
+
+    Ok(processes)
+}
+
+/// Every UWP process sharing `target`'s package family, including the target.
+///
+/// UWP apps commonly spread across several processes (the app, background tasks,
+/// runtime brokers); a whole-family dump captures all of them at once.
+pub fn family_tree(target: &ProcessInfo) -> windows::core::Result<Vec<ProcessInfo>> {
+    let mut members: Vec<ProcessInfo> = list_uwp_processes()?
+        .into_iter()
+        .filter(|p| p.family_name == target.family_name)
+        .collect();
+
+    // Keep the originally selected process first so its subfolder is obvious,
+    // then group the rest under their parent so related workers sit together.
+    members.sort_by_key(|p| (p.pid != target.pid, p.parent_pid, p.pid));
+    Ok(members)
+}
+
+/// Resolve a process's package family name, or `None` if it isn't packaged.
+fn package_family_name(pid: u32) -> Option<String> {
+    // The system idle/process PIDs can't be opened; skip them quietly.
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()? };
+
+    let family = query_family_name(handle);
+
+    unsafe {
+        let _ = CloseHandle(handle);
+    }
+
+    family
+}
+
+/// Call `GetPackageFamilyName` with the two-pass length/buffer protocol.
+fn query_family_name(handle: HANDLE) -> Option<String> {
+    let mut len: u32 = 0;
+    // First call sizes the buffer; unpackaged processes return a non-buffer
+    // error (APPMODEL_ERROR_NO_PACKAGE) and we bail out.
+    let rc = unsafe { GetPackageFamilyName(handle, &mut len, PWSTR::null()) };
+    if rc != ERROR_INSUFFICIENT_BUFFER || len == 0 {
+        return None;
+    }
+
+    let mut buffer = vec![0u16; len as usize];
+    let rc = unsafe { GetPackageFamilyName(handle, &mut len, PWSTR(buffer.as_mut_ptr())) };
+    if rc != ERROR_SUCCESS {
+        return None;
+    }
+
+    // `len` counts the trailing NUL.
+    let end = (len as usize).saturating_sub(1);
+    Some(String::from_utf16_lossy(&buffer[..end]))
+}
+
+/// Decode the NUL-terminated UTF-16 `szExeFile` field into a `String`.
+fn exe_name(raw: &[u16]) -> String {
+    let len = raw.iter().position(|&c| c == 0).unwrap_or(raw.len());
+    String::from_utf16_lossy(&raw[..len])
+}
@@ -1,14 +1,17 @@
 //! Core dumping logic - copies UWP package files to an accessible location
 
 use std::fs::{self, File};
-use std::io::{self, BufReader, BufWriter};
+use std::collections::HashMap;
+use std::io::{self, BufReader, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::time::Instant;
 
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use thiserror::Error;
-use uwpdumper_shared::IpcClient;
+use uwpdumper_shared::{IpcClient, Packet};
 use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
 use windows::core::PCWSTR;
 
@@ -37,10 +40,86 @@ pub enum DumperError {
 /// File entry with size information
 struct FileEntry {
     path: PathBuf,
-    #[allow(dead_code)] // Size is accumulated during collection for disk space check
     size: u64,
 }
 
+/// How the dumper delivers files to the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpMode {
+    /// Copy files into `TempState/DUMP`; the CLI copies them out afterwards.
+    LocalCopy,
+    /// Stream each file's bytes directly to the CLI over the IPC channel,
+    /// reconstructing the tree on the host. Needs no writable sandbox space.
+    Stream,
+    /// Write a single streamed ZIP archive into `TempState`, which the CLI then
+    /// retrieves. One open handle replaces tens of thousands of `CreateFile`s.
+    Archive {
+        /// Deflate entries instead of storing them uncompressed.
+        deflate: bool,
+    },
+}
+
+impl DumpMode {
+    /// Select the dump mode from the value the CLI sends over the IPC channel
+    /// before the dump starts. Unknown or empty values stream, which needs no
+    /// writable sandbox space.
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "copy" | "local" => DumpMode::LocalCopy,
+            "archive" => DumpMode::Archive { deflate: false },
+            "archive-deflate" | "zip" => DumpMode::Archive { deflate: true },
+            _ => DumpMode::Stream,
+        }
+    }
+}
+
+/// Approximate ZIP central-directory + local-header overhead per stored entry.
+const ZIP_PER_ENTRY_OVERHEAD: u64 = 128;
+
+/// File name of the content-addressed manifest written at the dump root.
+const MANIFEST_NAME: &str = "manifest.json";
+
+/// Size and SHA-256 of a single dumped file, keyed by relative path.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManifestEntry {
+    size: u64,
+    sha256: String,
+}
+
+/// Content-addressed manifest of a dump, used for incremental re-dumps and
+/// verification. Relative paths use the platform separator as written on disk.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    files: HashMap<String, ManifestEntry>,
+}
+
+impl Manifest {
+    /// Load a manifest from a dump root, returning an empty one if absent or
+    /// unreadable (a corrupt manifest simply forces a full re-dump).
+    fn load(dump_path: &Path) -> Self {
+        let path = dump_path.join(MANIFEST_NAME);
+        match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+            Err(_) => Manifest::default(),
+        }
+    }
+
+    /// Write the manifest to the dump root.
+    fn save(&self, dump_path: &Path) -> io::Result<()> {
+        let path = dump_path.join(MANIFEST_NAME);
+        let bytes = serde_json::to_vec_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, bytes)
+    }
+}
+
+/// Files at or above this size are streamed through the local-copy fallback
+/// even in [`DumpMode::Stream`], since a single large transfer over the IPC
+/// ring buffer can stall the host message loop.
+const STREAM_FALLBACK_THRESHOLD: u64 = 256 * 1024 * 1024;
+
+/// Chunk size for framed file-data packets pushed over the IPC channel.
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
 /// Get available disk space for a path
 fn get_available_space(path: &Path) -> io::Result<u64> {
     use std::os::windows::ffi::OsStrExt;
@@ -82,8 +161,11 @@ fn format_bytes(bytes: u64) -> String {
     }
 }
 
-/// Run the dumper, returns the path where files were dumped
-pub fn run(ipc: &mut IpcClient) -> Result<PathBuf, DumperError> {
+/// Run the dumper, returns the path where files were dumped.
+///
+/// `verify` re-reads and re-hashes the loose-file dump against its manifest; it
+/// doubles read I/O so it is opt-in (the CLI sets it from `--verify-dump`).
+pub fn run(ipc: &mut IpcClient, mode: DumpMode, verify: bool) -> Result<PathBuf, DumperError> {
     ipc.info("Retrieving package information...");
 
     let package = match CurrentPackage::current() {
@@ -106,9 +188,21 @@ pub fn run(ipc: &mut IpcClient) -> Result<PathBuf, DumperError> {
 
     ipc.info(&format!("Dump Path: {}", dump_path.display()));
 
-    if dump_path.exists() {
+    // Load any prior manifest so a dump into an existing DUMP/ can skip
+    // unchanged files instead of re-delivering everything. Only the loose-file
+    // path honours it: its manifest and destination share the sandbox, so a
+    // matching hash and an existing `dest` prove the file is present. The
+    // streaming path cannot skip - its manifest lives in the sandbox while the
+    // files live on the host, so a stale manifest would silently omit files the
+    // host no longer has (e.g. a re-dump to a different `--output`).
+    let prior_manifest = Manifest::load(&dump_path);
+    let incremental = matches!(mode, DumpMode::LocalCopy) && !prior_manifest.files.is_empty();
+
+    if dump_path.exists() && !incremental {
         ipc.info("Cleaning up previous dump...");
         fs::remove_dir_all(&dump_path)?;
+    } else if incremental {
+        ipc.info("Existing manifest found; performing incremental re-dump...");
     }
 
     fs::create_dir_all(&dump_path)?;
@@ -117,7 +211,7 @@ pub fn run(ipc: &mut IpcClient) -> Result<PathBuf, DumperError> {
 
     ipc.info("Scanning package files...");
 
-    let (files, total_size) = collect_files_with_progress(&package.package_path, ipc)?;
+    let (mut files, total_size) = collect_files_with_progress(&package.package_path, ipc)?;
     let total = files.len() as u32;
 
     // Sync to ensure CLI sees scan complete
@@ -129,8 +223,35 @@ pub fn run(ipc: &mut IpcClient) -> Result<PathBuf, DumperError> {
         format_bytes(total_size)
     ));
 
-    // Check available disk space (require 10% buffer for safety)
-    let required_space = total_size + (total_size / 10);
+    // Longest-processing-time-first: dispatch the biggest files earliest so
+    // multi-gigabyte game assets start immediately and the tail of small files
+    // packs around them. Applies to every mode, including streaming, where it
+    // gets the large-file fallback transfers moving before the small ones.
+    files.sort_unstable_by(|a, b| b.size.cmp(&a.size));
+
+    // In streaming mode we push bytes straight to the host and never stage a
+    // local copy, so the sandbox disk-space check is unnecessary.
+    if mode == DumpMode::Stream {
+        return stream_files(
+            ipc,
+            &package,
+            &files,
+            total,
+            total_size,
+            start_time,
+            &dump_path,
+            &prior_manifest,
+            incremental,
+        );
+    }
+
+    // Check available disk space. Loose files get the usual 10% buffer; a
+    // stored ZIP needs roughly the same bytes plus per-entry central-directory
+    // overhead, so size the requirement for whichever artifact we'll write.
+    let required_space = match mode {
+        DumpMode::Archive { .. } => total_size + total as u64 * ZIP_PER_ENTRY_OVERHEAD,
+        _ => total_size + (total_size / 10),
+    };
     match get_available_space(&dump_path) {
         Ok(available) => {
             if available < required_space {
@@ -154,6 +275,11 @@ pub fn run(ipc: &mut IpcClient) -> Result<PathBuf, DumperError> {
         }
     }
 
+    // Archive mode writes a single streamed ZIP instead of loose files.
+    if let DumpMode::Archive { deflate } = mode {
+        return archive_files(ipc, &package, &files, total, start_time, &temp_state, deflate);
+    }
+
     // Pre-create all unique directories first
     let mut dirs: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
     for file in &files {
@@ -183,11 +309,21 @@ pub fn run(ipc: &mut IpcClient) -> Result<PathBuf, DumperError> {
 
     ipc.info(&format!("Copying {} files (parallel)...", total));
 
-    // Set initial progress in shared memory (CLI will poll this)
-    ipc.set_progress(0, total);
+    // Report progress in KiB copied / total KiB so a single large file shows
+    // steady movement rather than appearing to stall at per-file granularity.
+    let total_kib = (total_size / 1024).max(1) as u32;
+    ipc.set_progress(0, total_kib);
 
-    let processed = AtomicU32::new(0);
+    let bytes_done = std::sync::atomic::AtomicU64::new(0);
+    let bump_progress = |delta: u64| {
+        let done = bytes_done.fetch_add(delta, Ordering::Relaxed) + delta;
+        ipc.set_progress((done / 1024) as u32, total_kib);
+    };
+
+    let skipped = AtomicU32::new(0);
     let failed_files: std::sync::Mutex<Vec<(PathBuf, String)>> = std::sync::Mutex::new(Vec::new());
+    let manifest_entries: std::sync::Mutex<HashMap<String, ManifestEntry>> =
+        std::sync::Mutex::new(HashMap::new());
 
     // Copy files in parallel - update progress atomically in shared memory
     files.par_iter().for_each(|file| {
@@ -196,22 +332,77 @@ pub fn run(ipc: &mut IpcClient) -> Result<PathBuf, DumperError> {
             .strip_prefix(&package.package_path)
             .unwrap_or(&file.path);
         let dest = dump_path.join(relative);
-
-        if let Err(e) = copy_file_buffered(&file.path, &dest)
-            && let Ok(mut failed) = failed_files.lock()
-        {
-            failed.push((relative.to_path_buf(), e.to_string()));
+        let key = relative.to_string_lossy().to_string();
+
+        // Incremental skip: if the prior manifest records this path with the
+        // same size and content hash and the destination still exists, there's
+        // nothing to copy.
+        let buf_size = if file.size >= LARGE_FILE_THRESHOLD {
+            LARGE_COPY_BUFFER
+        } else {
+            SMALL_COPY_BUFFER
+        };
+
+        let prior = prior_manifest.files.get(&key);
+        let result = if let Some(prior) = prior.filter(|p| p.size == file.size && dest.exists()) {
+            match hash_file(&file.path) {
+                Ok(hash) if hash == prior.sha256 => {
+                    skipped.fetch_add(1, Ordering::Relaxed);
+                    // Count skipped bytes so the byte-progress bar still advances.
+                    bump_progress(file.size);
+                    Ok(hash)
+                }
+                Ok(_) => copy_file_with_progress(&file.path, &dest, buf_size, |n| bump_progress(n as u64)),
+                Err(e) => Err(e),
+            }
+        } else {
+            copy_file_with_progress(&file.path, &dest, buf_size, |n| bump_progress(n as u64))
+        };
+
+        match result {
+            Ok(sha256) => {
+                if let Ok(mut entries) = manifest_entries.lock() {
+                    entries.insert(
+                        key,
+                        ManifestEntry {
+                            size: file.size,
+                            sha256,
+                        },
+                    );
+                }
+            }
+            Err(e) => {
+                if let Ok(mut failed) = failed_files.lock() {
+                    failed.push((relative.to_path_buf(), e.to_string()));
+                }
+            }
         }
 
-        // Update progress in shared memory (CLI polls this directly)
-        let current = processed.fetch_add(1, Ordering::Relaxed) + 1;
-        ipc.set_progress(current, total);
+        // Progress is owned by `bump_progress` (KiB copied / total KiB); a
+        // per-file `set_progress` here would overwrite it with a
+        // different-denominator file count and make the bar jump backwards.
     });
 
     // Sync to ensure CLI sees 100%
-    ipc.set_progress(total, total);
+    ipc.set_progress(total_kib, total_kib);
     ipc.sync();
 
+    // Persist the content-addressed manifest for future incremental dumps.
+    let manifest = Manifest {
+        files: manifest_entries.into_inner().unwrap_or_default(),
+    };
+    if let Err(e) = manifest.save(&dump_path) {
+        ipc.warn(&format!("Failed to write manifest: {}", e));
+    }
+
+    let skipped_count = skipped.load(Ordering::Relaxed);
+    if skipped_count > 0 {
+        ipc.info(&format!(
+            "Skipped {} unchanged files (incremental)",
+            skipped_count
+        ));
+    }
+
     let elapsed = start_time.elapsed();
     let failed = failed_files.into_inner().unwrap_or_default();
     let final_errors = failed.len() as u32;
@@ -238,11 +429,325 @@ pub fn run(ipc: &mut IpcClient) -> Result<PathBuf, DumperError> {
         }
     }
 
+    // Optionally verify the dump against the manifest we just wrote, reporting
+    // any mismatches (partial writes, disk corruption) to the host. Gated
+    // because it re-reads every file.
+    if verify {
+        verify_dump(ipc, &dump_path, &manifest);
+    }
+
     ipc.info(&format!("Output: {}", dump_path.display()));
 
     Ok(dump_path)
 }
 
+/// Re-read every destination file and confirm its hash against the manifest.
+///
+/// Mismatches and missing files are reported through `ipc.error`; a clean dump
+/// reports a single success line.
+fn verify_dump(ipc: &mut IpcClient, dump_path: &Path, manifest: &Manifest) {
+    ipc.info("Verifying dump against manifest...");
+
+    let total = manifest.files.len() as u32;
+    ipc.set_progress(0, total);
+
+    let mut mismatches = 0u32;
+    for (i, (relative, entry)) in manifest.files.iter().enumerate() {
+        let dest = dump_path.join(relative);
+        match hash_file(&dest) {
+            Ok(hash) if hash == entry.sha256 => {}
+            Ok(_) => {
+                mismatches += 1;
+                ipc.error(&format!("Hash mismatch: {}", relative));
+            }
+            Err(e) => {
+                mismatches += 1;
+                ipc.error(&format!("Cannot verify {}: {}", relative, e));
+            }
+        }
+        ipc.set_progress(i as u32 + 1, total);
+    }
+
+    ipc.set_progress(total, total);
+    ipc.sync();
+
+    if mismatches == 0 {
+        ipc.success(&format!("Verified {} files against manifest", total));
+    } else {
+        ipc.warn(&format!("{} files failed verification", mismatches));
+    }
+}
+
+/// Stream every file directly to the host over the IPC channel.
+///
+/// Each file is framed as a header packet (relative path + length) followed by
+/// chunked data packets and a trailing end packet, mirroring the read/write
+/// framing used by the shared pipe transport. The CLI reconstructs the tree on
+/// the host, so no writable sandbox space is required. Very large single files
+/// fall back to the local-copy path to avoid stalling the host loop.
+#[allow(clippy::too_many_arguments)]
+fn stream_files(
+    ipc: &mut IpcClient,
+    package: &CurrentPackage,
+    files: &[FileEntry],
+    total: u32,
+    total_size: u64,
+    start_time: Instant,
+    dump_path: &Path,
+    prior_manifest: &Manifest,
+    incremental: bool,
+) -> Result<PathBuf, DumperError> {
+    ipc.info(&format!(
+        "Streaming {} files ({}) to host...",
+        total,
+        format_bytes(total_size)
+    ));
+    if incremental {
+        ipc.info("Existing manifest found; streaming only changed files...");
+    }
+
+    // Report progress in KiB streamed / total KiB, like the loose-file path, so
+    // a single large file shows steady movement rather than appearing to stall
+    // at per-file granularity.
+    let total_kib = (total_size / 1024).max(1) as u32;
+    ipc.set_progress(0, total_kib);
+    let bytes_done = std::sync::atomic::AtomicU64::new(0);
+
+    let mut copied = 0u32;
+    let mut skipped = 0u32;
+    let mut failed: Vec<(PathBuf, String)> = Vec::new();
+    let mut manifest_entries: HashMap<String, ManifestEntry> = HashMap::new();
+
+    for file in files.iter() {
+        let relative = file
+            .path
+            .strip_prefix(&package.package_path)
+            .unwrap_or(&file.path);
+        let key = relative.to_string_lossy().to_string();
+
+        if file.size >= STREAM_FALLBACK_THRESHOLD {
+            // Fallback: stage very large files locally so a single multi-gigabyte
+            // transfer can't monopolise the IPC ring buffer.
+            let dest = dump_path.join(relative);
+            if let Some(parent) = dest.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            match copy_file_buffered(&file.path, &dest) {
+                Ok(sha256) => {
+                    copied += 1;
+                    manifest_entries.insert(key, ManifestEntry { size: file.size, sha256 });
+                }
+                Err(e) => failed.push((relative.to_path_buf(), e.to_string())),
+            }
+            let done = bytes_done.fetch_add(file.size, Ordering::Relaxed) + file.size;
+            ipc.set_progress((done / 1024) as u32, total_kib);
+        } else if let Some(prior) = prior_manifest
+            .files
+            .get(&key)
+            .filter(|p| incremental && p.size == file.size)
+        {
+            // Incremental skip: if the host already received this exact file on
+            // a previous run (same size and content hash), don't re-stream it.
+            match hash_file(&file.path) {
+                Ok(hash) if hash == prior.sha256 => {
+                    skipped += 1;
+                    // Count skipped bytes so the byte-progress bar still advances.
+                    let done = bytes_done.fetch_add(file.size, Ordering::Relaxed) + file.size;
+                    ipc.set_progress((done / 1024) as u32, total_kib);
+                    manifest_entries.insert(key, ManifestEntry { size: file.size, sha256: hash });
+                }
+                Ok(_) => match stream_file(ipc, relative, &file.path, &bytes_done, total_kib) {
+                    Ok(sha256) => {
+                        copied += 1;
+                        manifest_entries.insert(key, ManifestEntry { size: file.size, sha256 });
+                    }
+                    Err(e) => failed.push((relative.to_path_buf(), e.to_string())),
+                },
+                Err(e) => failed.push((relative.to_path_buf(), e.to_string())),
+            }
+        } else {
+            match stream_file(ipc, relative, &file.path, &bytes_done, total_kib) {
+                Ok(sha256) => {
+                    copied += 1;
+                    manifest_entries.insert(key, ManifestEntry { size: file.size, sha256 });
+                }
+                Err(e) => failed.push((relative.to_path_buf(), e.to_string())),
+            }
+        }
+    }
+
+    ipc.set_progress(total_kib, total_kib);
+    ipc.sync();
+
+    // Persist the content-addressed manifest of what was delivered. A later
+    // streaming run does not skip files against it: `run` only enables the
+    // incremental skip on the loose-file path.
+    let manifest = Manifest { files: manifest_entries };
+    if let Err(e) = manifest.save(dump_path) {
+        ipc.warn(&format!("Failed to write manifest: {}", e));
+    }
+
+    if skipped > 0 {
+        ipc.info(&format!("Skipped {} unchanged files (incremental)", skipped));
+    }
+
+    let elapsed = start_time.elapsed();
+    ipc.success(&format!(
+        "Streamed {} files ({} errors) in {:.1}s",
+        copied,
+        failed.len(),
+        elapsed.as_secs_f64()
+    ));
+
+    log_failed_files(ipc, &failed);
+
+    Ok(dump_path.to_path_buf())
+}
+
+/// Write every file into a single streamed ZIP archive in `TempState`.
+///
+/// One open handle replaces the thousands of per-file `CreateFile` calls the
+/// loose-file path makes, which sidesteps the scattered failures recorded in
+/// `failed_files`. Returns the path to the written `.zip`.
+fn archive_files(
+    ipc: &mut IpcClient,
+    package: &CurrentPackage,
+    files: &[FileEntry],
+    total: u32,
+    start_time: Instant,
+    temp_state: &Path,
+    deflate: bool,
+) -> Result<PathBuf, DumperError> {
+    use zip::write::SimpleFileOptions;
+    use zip::{CompressionMethod, ZipWriter};
+
+    let archive_path = temp_state.join("DUMP.zip");
+    if archive_path.exists() {
+        fs::remove_file(&archive_path)?;
+    }
+
+    ipc.info(&format!("Writing archive: {}", archive_path.display()));
+
+    let method = if deflate {
+        CompressionMethod::Deflated
+    } else {
+        CompressionMethod::Stored
+    };
+    let options = SimpleFileOptions::default()
+        .compression_method(method)
+        .large_file(true);
+
+    let archive_file = File::create(to_extended_path(&archive_path))?;
+    let mut zip = ZipWriter::new(BufWriter::with_capacity(64 * 1024, archive_file));
+
+    ipc.set_progress(0, total);
+
+    let mut written = 0u32;
+    let mut failed: Vec<(PathBuf, String)> = Vec::new();
+
+    for (i, file) in files.iter().enumerate() {
+        let relative = file
+            .path
+            .strip_prefix(&package.package_path)
+            .unwrap_or(&file.path);
+        // ZIP entries always use forward slashes for the path separator.
+        let name = relative.to_string_lossy().replace('\\', "/");
+
+        match add_zip_entry(&mut zip, &name, &file.path, options) {
+            Ok(()) => written += 1,
+            Err(e) => failed.push((relative.to_path_buf(), e.to_string())),
+        }
+
+        ipc.set_progress(i as u32 + 1, total);
+    }
+
+    zip.finish().map_err(|e| io::Error::other(e.to_string()))?;
+
+    ipc.set_progress(total, total);
+    ipc.sync();
+
+    let elapsed = start_time.elapsed();
+    ipc.success(&format!(
+        "Archived {} files ({} errors) in {:.1}s",
+        written,
+        failed.len(),
+        elapsed.as_secs_f64()
+    ));
+
+    log_failed_files(ipc, &failed);
+    ipc.info(&format!("Output: {}", archive_path.display()));
+
+    Ok(archive_path)
+}
+
+/// Stream a single file into the ZIP writer.
+fn add_zip_entry(
+    zip: &mut zip::ZipWriter<BufWriter<File>>,
+    name: &str,
+    src: &Path,
+    options: zip::write::SimpleFileOptions,
+) -> io::Result<()> {
+    zip.start_file(name, options)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    let src_file = File::open(to_extended_path(src))?;
+    let mut reader = BufReader::with_capacity(64 * 1024, src_file);
+    io::copy(&mut reader, zip)?;
+    Ok(())
+}
+
+/// Stream a single file to the host as framed file-data packets, returning the
+/// hex-encoded SHA-256 of the bytes sent so the caller can record a manifest.
+///
+/// Each chunk bumps `bytes_done` and reports KiB streamed so a multi-gigabyte
+/// file advances the progress bar instead of stalling it until the next file.
+fn stream_file(
+    ipc: &mut IpcClient,
+    relative: &Path,
+    src: &Path,
+    bytes_done: &std::sync::atomic::AtomicU64,
+    total_kib: u32,
+) -> io::Result<String> {
+    let src_extended = to_extended_path(src);
+    let file = File::open(&src_extended)?;
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let mut reader = BufReader::with_capacity(STREAM_CHUNK_SIZE, file);
+
+    ipc.push_packet(Packet::file_header(&relative.to_string_lossy(), size));
+
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; STREAM_CHUNK_SIZE];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        ipc.push_packet(Packet::file_chunk(&buffer[..read]));
+        let done = bytes_done.fetch_add(read as u64, Ordering::Relaxed) + read as u64;
+        ipc.set_progress((done / 1024) as u32, total_kib);
+    }
+
+    ipc.push_packet(Packet::file_end());
+    Ok(hex_digest(&hasher.finalize()))
+}
+
+/// Report failed files through the IPC warn channel (capped to avoid flooding).
+fn log_failed_files(ipc: &mut IpcClient, failed: &[(PathBuf, String)]) {
+    if failed.is_empty() {
+        return;
+    }
+    let show_count = failed.len().min(10);
+    for (path, error) in failed.iter().take(show_count) {
+        ipc.warn(&format!("Failed: {} - {}", path.display(), error));
+    }
+    if failed.len() > show_count {
+        ipc.warn(&format!(
+            "... and {} more failed files",
+            failed.len() - show_count
+        ));
+    }
+}
+
 /// Recursively collect all files in a directory with progress updates
 /// Returns (files, total_size)
 fn collect_files_with_progress(dir: &Path, ipc: &IpcClient) -> io::Result<(Vec<FileEntry>, u64)> {
@@ -320,8 +825,32 @@ fn to_extended_path(path: &Path) -> PathBuf {
     }
 }
 
-/// Copy file using buffered streaming (no attribute preservation, avoids EFS issues)
-fn copy_file_buffered(src: &Path, dest: &Path) -> io::Result<u64> {
+/// Files at or above this size use a large-block copy for throughput.
+const LARGE_FILE_THRESHOLD: u64 = 16 * 1024 * 1024;
+/// Block size for the large-file copy loop.
+const LARGE_COPY_BUFFER: usize = 8 * 1024 * 1024;
+/// Block size for the default buffered copy.
+const SMALL_COPY_BUFFER: usize = 64 * 1024;
+
+/// Copy file using buffered streaming (no attribute preservation, avoids EFS
+/// issues), computing a SHA-256 of the bytes as they stream through.
+///
+/// Returns the hex-encoded digest of the copied file.
+fn copy_file_buffered(src: &Path, dest: &Path) -> io::Result<String> {
+    copy_file_with_progress(src, dest, SMALL_COPY_BUFFER, |_| {})
+}
+
+/// Copy a file in `buf_size` blocks, hashing the bytes and reporting each
+/// block's length to `on_bytes` so the caller can track byte-level progress.
+///
+/// Large files pass a multi-megabyte `buf_size` to keep a single rayon worker
+/// saturated instead of paying per-64-KiB syscall overhead.
+fn copy_file_with_progress(
+    src: &Path,
+    dest: &Path,
+    buf_size: usize,
+    mut on_bytes: impl FnMut(usize),
+) -> io::Result<String> {
     // Use extended-length paths to handle long paths
     let src_extended = to_extended_path(src);
     let dest_extended = to_extended_path(dest);
@@ -329,8 +858,47 @@ fn copy_file_buffered(src: &Path, dest: &Path) -> io::Result<u64> {
     let src_file = File::open(&src_extended)?;
     let dest_file = File::create(&dest_extended)?;
 
-    let mut reader = BufReader::with_capacity(64 * 1024, src_file);
-    let mut writer = BufWriter::with_capacity(64 * 1024, dest_file);
+    let mut reader = BufReader::with_capacity(buf_size, src_file);
+    let mut writer = BufWriter::with_capacity(buf_size, dest_file);
 
-    io::copy(&mut reader, &mut writer)
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; buf_size];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        writer.write_all(&buffer[..read])?;
+        on_bytes(read);
+    }
+    writer.flush()?;
+
+    Ok(hex_digest(&hasher.finalize()))
+}
+
+/// Hash a file's contents without copying it, returning the hex-encoded digest.
+fn hash_file(path: &Path) -> io::Result<String> {
+    let file = File::open(to_extended_path(path))?;
+    let mut reader = BufReader::with_capacity(64 * 1024, file);
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex_digest(&hasher.finalize()))
+}
+
+/// Format a SHA-256 digest as a lowercase hex string.
+fn hex_digest(digest: &[u8]) -> String {
+    use std::fmt::Write;
+    let mut s = String::with_capacity(digest.len() * 2);
+    for byte in digest {
+        let _ = write!(s, "{:02x}", byte);
+    }
+    s
 }
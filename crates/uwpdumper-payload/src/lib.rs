@@ -66,8 +66,12 @@ extern "system" fn dumper_thread(_param: *mut c_void) -> u32 {
         std::thread::sleep(std::time::Duration::from_millis(10));
     }
 
-    // Run the dumper
-    let exit_code = match dumper::run(&mut ipc) {
+    // Run the dumper in the mode the CLI sent over IPC before releasing us; the
+    // injected payload can't read the injector's environment. Streaming is the
+    // default when nothing was set.
+    let mode = dumper::DumpMode::parse(&ipc.dump_mode());
+    let verify = ipc.verify_requested();
+    let exit_code = match dumper::run(&mut ipc, mode, verify) {
         Ok(dump_path) => {
             // Send dump path in Complete packet so CLI can copy to custom output
             ipc.push_packet(uwpdumper_shared::Packet::complete(